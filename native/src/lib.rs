@@ -1,13 +1,21 @@
 //! Native Rust acceleration for PS2 texture sorting.
 //!
 //! Provides high-performance implementations of:
-//! - Lanczos image upscaling
+//! - Lanczos image upscaling, and a reusable `Resizer` with precomputed
+//!   coefficient tables supporting arbitrary resizing, multiple filters,
+//!   and optional gamma-correct (linear-light) resampling
 //! - Image feature extraction (perceptual hash, color histogram, edge density)
+//! - Palette quantization (median-cut + k-means + Floyd-Steinberg dithering)
+//! - Near-duplicate clustering over perceptual hashes (union-find with
+//!   pigeonhole bucketing) and single-query nearest-neighbor lookup
 //! - Batch parallel image processing via Rayon
 //!
 //! Built with PyO3 for seamless Python integration.
 
+use std::collections::HashMap;
+
 use pyo3::prelude::*;
+use pyo3::types::PyAny;
 use rayon::prelude::*;
 
 /// Clamp a value to [0, 255] and convert to u8.
@@ -34,161 +42,523 @@ fn lanczos_weight(x: f64, a: f64) -> f64 {
 // Upscaling
 // ---------------------------------------------------------------------------
 
-/// Upscale a flat RGB/RGBA pixel buffer using Lanczos-3 interpolation.
+/// A resampling kernel supported by `Resizer`.
+#[derive(Clone, Copy)]
+enum FilterKind {
+    Lanczos3,
+    Bilinear,
+    CatmullRom,
+}
+
+impl FilterKind {
+    fn from_str(name: &str) -> PyResult<Self> {
+        match name {
+            "lanczos3" => Ok(FilterKind::Lanczos3),
+            "bilinear" => Ok(FilterKind::Bilinear),
+            "catmull-rom" => Ok(FilterKind::CatmullRom),
+            other => Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "unknown filter '{}': expected 'lanczos3', 'bilinear', or 'catmull-rom'",
+                other
+            ))),
+        }
+    }
+
+    /// Kernel half-width in source-pixel units at native (1x) scale.
+    fn support(&self) -> f64 {
+        match self {
+            FilterKind::Lanczos3 => 3.0,
+            FilterKind::Bilinear => 1.0,
+            FilterKind::CatmullRom => 2.0,
+        }
+    }
+
+    fn weight(&self, x: f64) -> f64 {
+        match self {
+            FilterKind::Lanczos3 => lanczos_weight(x, 3.0),
+            FilterKind::Bilinear => {
+                let ax = x.abs();
+                if ax < 1.0 {
+                    1.0 - ax
+                } else {
+                    0.0
+                }
+            }
+            FilterKind::CatmullRom => catmull_rom_weight(x),
+        }
+    }
+}
+
+/// Keys bicubic kernel with B=0, C=0.5 (Catmull-Rom).
+fn catmull_rom_weight(x: f64) -> f64 {
+    let (b, c) = (0.0f64, 0.5f64);
+    let ax = x.abs();
+    if ax < 1.0 {
+        ((12.0 - 9.0 * b - 6.0 * c) * ax.powi(3)
+            + (-18.0 + 12.0 * b + 6.0 * c) * ax.powi(2)
+            + (6.0 - 2.0 * b))
+            / 6.0
+    } else if ax < 2.0 {
+        ((-b - 6.0 * c) * ax.powi(3)
+            + (6.0 * b + 30.0 * c) * ax.powi(2)
+            + (-12.0 * b - 48.0 * c) * ax
+            + (8.0 * b + 24.0 * c))
+            / 6.0
+    } else {
+        0.0
+    }
+}
+
+/// Precompute, for each output coordinate along one axis, the list of
+/// `(source_index, weight)` contributions sampled from the given filter.
+/// When downscaling (`dst_len < src_len`), the kernel support is widened by
+/// the inverse scale ratio and the sample position scaled down to match, so
+/// the filter acts as a low-pass/anti-alias filter instead of point-sampling.
+fn build_coeffs(src_len: usize, dst_len: usize, filter: FilterKind) -> Vec<Vec<(usize, f64)>> {
+    let scale = dst_len as f64 / src_len as f64;
+    let (support, filter_scale) = if scale < 1.0 {
+        (filter.support() / scale, scale)
+    } else {
+        (filter.support(), 1.0)
+    };
+    (0..dst_len)
+        .map(|dst_i| {
+            let src_center = (dst_i as f64 + 0.5) / scale - 0.5;
+            let x0 = (src_center - support).floor().max(0.0) as usize;
+            let x1 =
+                ((src_center + support).ceil() as isize).clamp(0, src_len as isize - 1) as usize;
+            let mut weights: Vec<(usize, f64)> = Vec::new();
+            let mut w_sum = 0.0f64;
+            for sx in x0..=x1 {
+                let w = filter.weight((src_center - sx as f64) * filter_scale);
+                if w.abs() > f64::EPSILON {
+                    weights.push((sx, w));
+                    w_sum += w;
+                }
+            }
+            if w_sum.abs() > f64::EPSILON {
+                for entry in weights.iter_mut() {
+                    entry.1 /= w_sum;
+                }
+            }
+            weights
+        })
+        .collect()
+}
+
+/// Linearize a single normalized sRGB channel value (`c` in [0, 1]).
+fn srgb_to_linear_scalar(c: f64) -> f64 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Re-encode a single normalized linear channel value (`l` in [0, 1]) to sRGB.
+fn linear_to_srgb_scalar(l: f64) -> f64 {
+    if l <= 0.0031308 {
+        12.92 * l
+    } else {
+        1.055 * l.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// 256-entry forward (sRGB -> linear) lookup table, indexed by raw byte value.
+fn build_srgb_to_linear_lut() -> [f64; 256] {
+    let mut lut = [0.0f64; 256];
+    for (i, entry) in lut.iter_mut().enumerate() {
+        *entry = srgb_to_linear_scalar(i as f64 / 255.0);
+    }
+    lut
+}
+
+/// Linearize raw sRGB pixel bytes to normalized linear-light `f64` values.
+///
+/// The alpha channel, if present, is already linear and is passed through
+/// as a plain `[0, 1]` normalization rather than the sRGB transfer function.
 ///
 /// Parameters
 /// ----------
 /// data : bytes
 ///     Raw pixel data in row-major order (R, G, B[, A] per pixel).
-/// width : int
-///     Source image width in pixels.
-/// height : int
-///     Source image height in pixels.
-/// channels : int
+/// channels : int, default 3
 ///     Number of channels (3 for RGB, 4 for RGBA).
-/// scale : int
-///     Integer scale factor (e.g. 2, 4, 8).
 ///
 /// Returns
 /// -------
-/// tuple[bytes, int, int]
-///     (upscaled_data, new_width, new_height)
+/// list[float]
+///     Linear-light values in [0, 1], same length as `data`.
 #[pyfunction]
-fn lanczos_upscale(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    channels: usize,
-    scale: usize,
-) -> PyResult<(Vec<u8>, usize, usize)> {
+#[pyo3(signature = (data, channels=3))]
+fn srgb_to_linear(data: &[u8], channels: usize) -> PyResult<Vec<f64>> {
     if channels != 3 && channels != 4 {
         return Err(pyo3::exceptions::PyValueError::new_err(
             "channels must be 3 (RGB) or 4 (RGBA)",
         ));
     }
-    let expected_len = width * height * channels;
-    if data.len() != expected_len {
-        return Err(pyo3::exceptions::PyValueError::new_err(format!(
-            "data length {} does not match {}x{}x{}={}",
-            data.len(),
-            width,
-            height,
-            channels,
-            expected_len,
-        )));
+    if !data.len().is_multiple_of(channels) {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "data length must be a multiple of channels",
+        ));
     }
-    if scale == 0 {
+    let alpha_idx = if channels == 4 { Some(3) } else { None };
+    Ok(data
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            if Some(i % channels) == alpha_idx {
+                v as f64 / 255.0
+            } else {
+                srgb_to_linear_scalar(v as f64 / 255.0)
+            }
+        })
+        .collect())
+}
+
+/// Re-encode normalized linear-light `f64` values to raw sRGB pixel bytes.
+///
+/// Parameters
+/// ----------
+/// data : list[float]
+///     Linear-light values in [0, 1], row-major (R, G, B[, A] per pixel).
+/// channels : int, default 3
+///     Number of channels (3 for RGB, 4 for RGBA).
+///
+/// Returns
+/// -------
+/// bytes
+///     Raw sRGB pixel data, same length as `data`.
+#[pyfunction]
+#[pyo3(signature = (data, channels=3))]
+fn linear_to_srgb(data: Vec<f64>, channels: usize) -> PyResult<Vec<u8>> {
+    if channels != 3 && channels != 4 {
         return Err(pyo3::exceptions::PyValueError::new_err(
-            "scale must be >= 1",
+            "channels must be 3 (RGB) or 4 (RGBA)",
+        ));
+    }
+    if !data.len().is_multiple_of(channels) {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "data length must be a multiple of channels",
         ));
     }
+    let alpha_idx = if channels == 4 { Some(3) } else { None };
+    Ok(data
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            if Some(i % channels) == alpha_idx {
+                clamp_u8(v * 255.0)
+            } else {
+                clamp_u8(linear_to_srgb_scalar(v) * 255.0)
+            }
+        })
+        .collect())
+}
 
-    let new_w = width * scale;
-    let new_h = height * scale;
-    let a: f64 = 3.0; // Lanczos-3
-
-    // Two-pass separable filter: horizontal then vertical.
-
-    // --- horizontal pass ---
-    let h_buf: Vec<u8> = (0..height)
-        .into_par_iter()
-        .flat_map_iter(|y| {
-            let row_off = y * width * channels;
-            (0..new_w).flat_map(move |x| {
-                let src_x = (x as f64 + 0.5) / scale as f64 - 0.5;
-                let x0 = (src_x.floor() as isize - a as isize + 1).max(0) as usize;
-                let x1 = ((src_x.floor() as isize + a as isize) as usize).min(width - 1);
-                let mut sums = vec![0.0f64; channels];
-                let mut w_sum = 0.0f64;
-                for sx in x0..=x1 {
-                    let w = lanczos_weight(src_x - sx as f64, a);
-                    w_sum += w;
-                    let off = row_off + sx * channels;
-                    for c in 0..channels {
-                        sums[c] += w * data[off + c] as f64;
+/// A reusable image resizer with precomputed separable filter coefficients.
+///
+/// Building a `Resizer` samples the chosen kernel once per output row and
+/// column; `resize` can then be called repeatedly (e.g. over a batch of
+/// same-sized textures) without recomputing weights.
+#[pyclass]
+struct Resizer {
+    #[pyo3(get)]
+    src_w: usize,
+    #[pyo3(get)]
+    src_h: usize,
+    #[pyo3(get)]
+    dst_w: usize,
+    #[pyo3(get)]
+    dst_h: usize,
+    #[pyo3(get)]
+    channels: usize,
+    #[pyo3(get)]
+    gamma_correct: bool,
+    x_coeffs: Vec<Vec<(usize, f64)>>,
+    y_coeffs: Vec<Vec<(usize, f64)>>,
+    srgb_to_linear_lut: [f64; 256],
+}
+
+#[pymethods]
+impl Resizer {
+    /// Parameters
+    /// ----------
+    /// src_w, src_h : int
+    ///     Source image dimensions in pixels.
+    /// dst_w, dst_h : int
+    ///     Target image dimensions in pixels (may be larger or smaller than
+    ///     the source, and independent per axis).
+    /// channels : int
+    ///     Number of channels (3 for RGB, 4 for RGBA).
+    /// filter : str, default "lanczos3"
+    ///     One of "lanczos3", "bilinear", or "catmull-rom".
+    /// gamma_correct : bool, default False
+    ///     Linearize sRGB samples before filtering and re-encode afterward,
+    ///     avoiding the dark fringes/halos that come from filtering raw
+    ///     sRGB bytes. The alpha channel, if present, is treated as already
+    ///     linear and left out of the transfer function.
+    #[new]
+    #[pyo3(signature = (src_w, src_h, dst_w, dst_h, channels, filter="lanczos3", gamma_correct=false))]
+    fn new(
+        src_w: usize,
+        src_h: usize,
+        dst_w: usize,
+        dst_h: usize,
+        channels: usize,
+        filter: &str,
+        gamma_correct: bool,
+    ) -> PyResult<Self> {
+        if channels != 3 && channels != 4 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "channels must be 3 (RGB) or 4 (RGBA)",
+            ));
+        }
+        if src_w == 0 || src_h == 0 || dst_w == 0 || dst_h == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "dimensions must be >= 1",
+            ));
+        }
+        let filter_kind = FilterKind::from_str(filter)?;
+        Ok(Resizer {
+            src_w,
+            src_h,
+            dst_w,
+            dst_h,
+            channels,
+            gamma_correct,
+            x_coeffs: build_coeffs(src_w, dst_w, filter_kind),
+            y_coeffs: build_coeffs(src_h, dst_h, filter_kind),
+            srgb_to_linear_lut: build_srgb_to_linear_lut(),
+        })
+    }
+
+    /// Resize a flat RGB/RGBA pixel buffer matching this resizer's source
+    /// dimensions to its target dimensions.
+    ///
+    /// Parameters
+    /// ----------
+    /// data : bytes
+    ///     Raw pixel data in row-major order (R, G, B[, A] per pixel).
+    ///
+    /// Returns
+    /// -------
+    /// bytes
+    ///     The resized pixel data.
+    fn resize(&self, data: &[u8]) -> PyResult<Vec<u8>> {
+        let expected_len = self.src_w * self.src_h * self.channels;
+        if data.len() != expected_len {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "data length {} does not match {}x{}x{}={}",
+                data.len(),
+                self.src_w,
+                self.src_h,
+                self.channels,
+                expected_len,
+            )));
+        }
+        Ok(self.resize_impl(data))
+    }
+}
+
+impl Resizer {
+    fn resize_impl(&self, data: &[u8]) -> Vec<u8> {
+        if self.gamma_correct {
+            self.resize_linear(data)
+        } else {
+            self.resize_srgb(data)
+        }
+    }
+
+    /// Separable two-pass resample over raw sRGB byte values: horizontal
+    /// then vertical, using the precomputed coefficient tables.
+    fn resize_srgb(&self, data: &[u8]) -> Vec<u8> {
+        let channels = self.channels;
+
+        let h_buf: Vec<u8> = (0..self.src_h)
+            .into_par_iter()
+            .flat_map_iter(|y| {
+                let row_off = y * self.src_w * channels;
+                self.x_coeffs.iter().flat_map(move |coeffs| {
+                    let mut sums = vec![0.0f64; channels];
+                    for &(sx, w) in coeffs {
+                        let off = row_off + sx * channels;
+                        for c in 0..channels {
+                            sums[c] += w * data[off + c] as f64;
+                        }
                     }
-                }
-                if w_sum.abs() > f64::EPSILON {
-                    for s in sums.iter_mut() {
-                        *s /= w_sum;
+                    sums.into_iter().map(clamp_u8)
+                })
+            })
+            .collect();
+
+        let dst_w = self.dst_w;
+        let h_buf_ref = &h_buf;
+        self.y_coeffs
+            .par_iter()
+            .flat_map_iter(|coeffs| {
+                (0..dst_w).flat_map(move |x| {
+                    let mut sums = vec![0.0f64; channels];
+                    for &(sy, w) in coeffs {
+                        let off = (sy * dst_w + x) * channels;
+                        for c in 0..channels {
+                            sums[c] += w * h_buf_ref[off + c] as f64;
+                        }
                     }
+                    sums.into_iter().map(clamp_u8)
+                })
+            })
+            .collect()
+    }
+
+    /// Same two-pass resample, but linearizing sRGB samples first and only
+    /// converting back to 8-bit sRGB at the final clamp step, so filtering
+    /// happens in linear light. The alpha channel (if present) is passed
+    /// through unconverted, since it is already linear.
+    fn resize_linear(&self, data: &[u8]) -> Vec<u8> {
+        let channels = self.channels;
+        let alpha_idx = if channels == 4 { Some(3) } else { None };
+
+        let linear: Vec<f64> = data
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| {
+                if Some(i % channels) == alpha_idx {
+                    v as f64 / 255.0
+                } else {
+                    self.srgb_to_linear_lut[v as usize]
                 }
-                sums.into_iter().map(clamp_u8)
             })
-        })
-        .collect();
+            .collect();
+        let linear_ref = &linear;
 
-    // --- vertical pass ---
-    let h_buf_ref = &h_buf;
-    let out: Vec<u8> = (0..new_h)
-        .into_par_iter()
-        .flat_map_iter(|y| {
-            let src_y = (y as f64 + 0.5) / scale as f64 - 0.5;
-            let y0 = (src_y.floor() as isize - a as isize + 1).max(0) as usize;
-            let y1 = ((src_y.floor() as isize + a as isize) as usize).min(height - 1);
-            (0..new_w).flat_map(move |x| {
-                let mut sums = vec![0.0f64; channels];
-                let mut w_sum = 0.0f64;
-                for sy in y0..=y1 {
-                    let w = lanczos_weight(src_y - sy as f64, a);
-                    w_sum += w;
-                    let off = (sy * new_w + x) * channels;
-                    for c in 0..channels {
-                        sums[c] += w * h_buf_ref[off + c] as f64;
+        let h_buf: Vec<f64> = (0..self.src_h)
+            .into_par_iter()
+            .flat_map_iter(|y| {
+                let row_off = y * self.src_w * channels;
+                self.x_coeffs.iter().flat_map(move |coeffs| {
+                    let mut sums = vec![0.0f64; channels];
+                    for &(sx, w) in coeffs {
+                        let off = row_off + sx * channels;
+                        for c in 0..channels {
+                            sums[c] += w * linear_ref[off + c];
+                        }
                     }
-                }
-                if w_sum.abs() > f64::EPSILON {
-                    for s in sums.iter_mut() {
-                        *s /= w_sum;
+                    sums.into_iter()
+                })
+            })
+            .collect();
+
+        let dst_w = self.dst_w;
+        let h_buf_ref = &h_buf;
+        let linear_out: Vec<f64> = self
+            .y_coeffs
+            .par_iter()
+            .flat_map_iter(|coeffs| {
+                (0..dst_w).flat_map(move |x| {
+                    let mut sums = vec![0.0f64; channels];
+                    for &(sy, w) in coeffs {
+                        let off = (sy * dst_w + x) * channels;
+                        for c in 0..channels {
+                            sums[c] += w * h_buf_ref[off + c];
+                        }
                     }
-                }
-                sums.into_iter().map(clamp_u8)
+                    sums.into_iter()
+                })
             })
-        })
-        .collect();
+            .collect();
 
-    Ok((out, new_w, new_h))
+        linear_out
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| {
+                if Some(i % channels) == alpha_idx {
+                    clamp_u8(v * 255.0)
+                } else {
+                    clamp_u8(linear_to_srgb_scalar(v) * 255.0)
+                }
+            })
+            .collect()
+    }
 }
 
-// ---------------------------------------------------------------------------
-// Feature extraction helpers
-// ---------------------------------------------------------------------------
-
-/// Compute a simple perceptual hash (pHash) of an image.
+/// Upscale a flat RGB/RGBA pixel buffer using Lanczos-3 interpolation.
 ///
-/// The image is down-sampled internally to 8x8 grayscale and the hash is
-/// computed from the DCT-like mean comparison, producing a 64-bit integer.
+/// Thin wrapper over `Resizer` kept for backward compatibility; prefer
+/// `Resizer` directly when resizing many same-sized images or when
+/// downscaling / non-integer scales are needed.
 ///
 /// Parameters
 /// ----------
 /// data : bytes
-///     Raw RGB pixel data (3 bytes per pixel, row-major).
+///     Raw pixel data in row-major order (R, G, B[, A] per pixel).
 /// width : int
-///     Image width.
+///     Source image width in pixels.
 /// height : int
-///     Image height.
+///     Source image height in pixels.
+/// channels : int
+///     Number of channels (3 for RGB, 4 for RGBA).
+/// scale : int
+///     Integer scale factor (e.g. 2, 4, 8).
+/// gamma_correct : bool, default False
+///     Linearize sRGB samples before filtering and re-encode afterward; see
+///     `Resizer`.
 ///
 /// Returns
 /// -------
-/// int
-///     64-bit perceptual hash.
+/// tuple[bytes, int, int]
+///     (upscaled_data, new_width, new_height)
 #[pyfunction]
-fn perceptual_hash(data: &[u8], width: usize, height: usize) -> PyResult<u64> {
-    if data.len() != width * height * 3 {
+#[pyo3(signature = (data, width, height, channels, scale, gamma_correct=false))]
+fn lanczos_upscale(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    scale: usize,
+    gamma_correct: bool,
+) -> PyResult<(Vec<u8>, usize, usize)> {
+    if scale == 0 {
         return Err(pyo3::exceptions::PyValueError::new_err(
-            "data length must equal width * height * 3 (RGB)",
+            "scale must be >= 1",
         ));
     }
-    // Down-sample to 8x8 grayscale using area averaging.
-    let mut gray8x8 = [0.0f64; 64];
-    let bw = width as f64 / 8.0;
-    let bh = height as f64 / 8.0;
-    for by in 0..8 {
-        for bx in 0..8 {
+    let new_w = width * scale;
+    let new_h = height * scale;
+    let resizer = Resizer::new(
+        width,
+        height,
+        new_w,
+        new_h,
+        channels,
+        "lanczos3",
+        gamma_correct,
+    )?;
+    let out = resizer.resize(data)?;
+    Ok((out, new_w, new_h))
+}
+
+// ---------------------------------------------------------------------------
+// Feature extraction helpers
+// ---------------------------------------------------------------------------
+
+/// Down-sample RGB pixel data to `out_w x out_h` grayscale using area
+/// averaging (box filter).
+fn downsample_grayscale(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    out_w: usize,
+    out_h: usize,
+) -> Vec<f64> {
+    let mut out = vec![0.0f64; out_w * out_h];
+    let bw = width as f64 / out_w as f64;
+    let bh = height as f64 / out_h as f64;
+    for by in 0..out_h {
+        for bx in 0..out_w {
             let y0 = (by as f64 * bh) as usize;
-            let y1 = (((by + 1) as f64 * bh) as usize).min(height);
+            let y1 = (((by + 1) as f64 * bh) as usize).min(height).max(y0 + 1);
             let x0 = (bx as f64 * bw) as usize;
-            let x1 = (((bx + 1) as f64 * bw) as usize).min(width);
+            let x1 = (((bx + 1) as f64 * bw) as usize).min(width).max(x0 + 1);
             let mut sum = 0.0f64;
             let mut count = 0u64;
             for y in y0..y1 {
@@ -201,36 +571,246 @@ fn perceptual_hash(data: &[u8], width: usize, height: usize) -> PyResult<u64> {
                     count += 1;
                 }
             }
-            gray8x8[by * 8 + bx] = if count > 0 { sum / count as f64 } else { 0.0 };
+            out[by * out_w + bx] = if count > 0 { sum / count as f64 } else { 0.0 };
         }
     }
-    // Compute hash: each bit is 1 if pixel > mean.
-    let mean: f64 = gray8x8.iter().sum::<f64>() / 64.0;
-    let mut hash: u64 = 0;
-    for (i, &val) in gray8x8.iter().enumerate() {
-        if val > mean {
-            hash |= 1u64 << i;
+    out
+}
+
+/// 1-D DCT-II of an `N`-sample row `x`: coefficient `k` is
+/// `sum_{i=0}^{N-1} x[i] * cos(pi / N * (i + 0.5) * k)`.
+fn dct_1d(input: &[f64]) -> Vec<f64> {
+    let n = input.len();
+    (0..n)
+        .map(|k| {
+            input
+                .iter()
+                .enumerate()
+                .map(|(x, &val)| {
+                    val * (std::f64::consts::PI / n as f64 * (x as f64 + 0.5) * k as f64).cos()
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Separable 2D DCT-II over an `n x n` grid: 1-D DCT across rows, then columns.
+fn dct_2d(grid: &[f64], n: usize) -> Vec<f64> {
+    let mut rows = vec![0.0f64; n * n];
+    for y in 0..n {
+        rows[y * n..(y + 1) * n].copy_from_slice(&dct_1d(&grid[y * n..(y + 1) * n]));
+    }
+    let mut out = vec![0.0f64; n * n];
+    for x in 0..n {
+        let col: Vec<f64> = (0..n).map(|y| rows[y * n + x]).collect();
+        let transformed = dct_1d(&col);
+        for y in 0..n {
+            out[y * n + x] = transformed[y];
+        }
+    }
+    out
+}
+
+/// Median of a slice of `f64` values; sorts a local copy.
+fn median_f64(values: &[f64]) -> f64 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = sorted.len();
+    if n == 0 {
+        return 0.0;
+    }
+    if n.is_multiple_of(2) {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    } else {
+        sorted[n / 2]
+    }
+}
+
+/// Compute the perceptual hash bits for an image, shared by `perceptual_hash`
+/// and `batch_perceptual_hash` so the batch path never needs the GIL.
+fn compute_phash_bits(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    algo: &str,
+    hash_size: usize,
+) -> PyResult<Vec<bool>> {
+    if width == 0 || height == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "dimensions must be >= 1",
+        ));
+    }
+    if data.len() != width * height * 3 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "data length must equal width * height * 3 (RGB)",
+        ));
+    }
+    if hash_size == 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "hash_size must be >= 1",
+        ));
+    }
+    let bits = match algo {
+        "average" => {
+            let grid = downsample_grayscale(data, width, height, hash_size, hash_size);
+            let mean: f64 = grid.iter().sum::<f64>() / grid.len() as f64;
+            grid.iter().map(|&v| v > mean).collect()
+        }
+        "dct" => {
+            let n = hash_size * 4;
+            let grid = downsample_grayscale(data, width, height, n, n);
+            let coeffs = dct_2d(&grid, n);
+            let mut low_freq: Vec<f64> = Vec::with_capacity(hash_size * hash_size - 1);
+            for y in 0..hash_size {
+                for x in 0..hash_size {
+                    if x == 0 && y == 0 {
+                        continue; // drop the DC term
+                    }
+                    low_freq.push(coeffs[y * n + x]);
+                }
+            }
+            let median = median_f64(&low_freq);
+            low_freq.iter().map(|&v| v > median).collect()
+        }
+        "dhash" => {
+            let w = hash_size + 1;
+            let grid = downsample_grayscale(data, width, height, w, hash_size);
+            let mut bits = Vec::with_capacity(hash_size * hash_size);
+            for y in 0..hash_size {
+                for x in 0..hash_size {
+                    bits.push(grid[y * w + x] > grid[y * w + x + 1]);
+                }
+            }
+            bits
+        }
+        "gradient" => {
+            let grid = downsample_grayscale(data, width, height, hash_size, hash_size);
+            let grads: Vec<f64> = (0..hash_size * hash_size)
+                .map(|i| {
+                    let (y, x) = (i / hash_size, i % hash_size);
+                    let cell = grid[i];
+                    let right = grid[y * hash_size + (x + 1).min(hash_size - 1)];
+                    let below = grid[(y + 1).min(hash_size - 1) * hash_size + x];
+                    (right - cell).abs() + (below - cell).abs()
+                })
+                .collect();
+            let median = median_f64(&grads);
+            grads.iter().map(|&v| v > median).collect()
+        }
+        other => {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "unknown algo '{}': expected 'average', 'dct', 'dhash', or 'gradient'",
+                other
+            )));
+        }
+    };
+    Ok(bits)
+}
+
+/// Pack hash bits (bit `i` set from entry `i`, LSB-first) into a Python
+/// value: a plain `int` for 64 bits or fewer, otherwise `bytes`.
+fn hash_bits_to_pyobject(py: Python<'_>, bits: &[bool]) -> PyResult<PyObject> {
+    if bits.len() <= 64 {
+        let mut hash: u64 = 0;
+        for (i, &b) in bits.iter().enumerate() {
+            if b {
+                hash |= 1u64 << i;
+            }
+        }
+        Ok(hash.into_py(py))
+    } else {
+        let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+        for (i, &b) in bits.iter().enumerate() {
+            if b {
+                bytes[i / 8] |= 1 << (i % 8);
+            }
         }
+        Ok(pyo3::types::PyBytes::new_bound(py, &bytes).into_py(py))
     }
-    Ok(hash)
 }
 
-/// Compute the Hamming distance between two 64-bit perceptual hashes.
+/// Compute a perceptual hash (pHash) of an image.
 ///
 /// Parameters
 /// ----------
-/// hash_a : int
+/// data : bytes
+///     Raw RGB pixel data (3 bytes per pixel, row-major).
+/// width : int
+///     Image width.
+/// height : int
+///     Image height.
+/// algo : str, default "average"
+///     One of "average" (mean comparison over a `hash_size x hash_size`
+///     down-sample), "dct" (frequency-domain DCT-II hash, robust to
+///     gamma/contrast shifts), "dhash" (adjacent-pixel gradient hash), or
+///     "gradient" (a middle ground between "average" and "dct").
+/// hash_size : int, default 8
+///     Side length of the hash grid; the hash has `hash_size**2` bits
+///     (`hash_size**2 - 1` for "dct", which drops the DC term).
+///
+/// Returns
+/// -------
+/// int | bytes
+///     The hash as a Python int for 64 bits or fewer, otherwise as bytes.
+#[pyfunction]
+#[pyo3(signature = (data, width, height, algo="average", hash_size=8))]
+fn perceptual_hash(
+    py: Python<'_>,
+    data: &[u8],
+    width: usize,
+    height: usize,
+    algo: &str,
+    hash_size: usize,
+) -> PyResult<PyObject> {
+    let bits = compute_phash_bits(data, width, height, algo, hash_size)?;
+    hash_bits_to_pyobject(py, &bits)
+}
+
+/// Compute the Hamming distance between two perceptual hashes.
+///
+/// Accepts the `int` or `bytes` hashes returned by `perceptual_hash`;
+/// both arguments must be the same representation and width.
+///
+/// Parameters
+/// ----------
+/// hash_a : int | bytes
 ///     First hash.
-/// hash_b : int
+/// hash_b : int | bytes
 ///     Second hash.
 ///
 /// Returns
 /// -------
 /// int
-///     Number of differing bits (0 = identical, 64 = maximally different).
+///     Number of differing bits (0 = identical).
 #[pyfunction]
-fn hamming_distance(hash_a: u64, hash_b: u64) -> u32 {
-    (hash_a ^ hash_b).count_ones()
+fn hamming_distance(hash_a: &Bound<'_, PyAny>, hash_b: &Bound<'_, PyAny>) -> PyResult<u32> {
+    let bytes_a = hash_to_bytes(hash_a)?;
+    let bytes_b = hash_to_bytes(hash_b)?;
+    if bytes_a.len() != bytes_b.len() {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "hashes must have the same representation and width to compare",
+        ));
+    }
+    Ok(bytes_a
+        .iter()
+        .zip(bytes_b.iter())
+        .map(|(a, b)| (a ^ b).count_ones())
+        .sum())
+}
+
+/// Extract the little-endian byte representation of a hash, accepting
+/// either a Python `int` (as produced for hashes up to 64 bits) or `bytes`
+/// (as produced for wider hashes).
+fn hash_to_bytes(obj: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(v) = obj.extract::<u64>() {
+        return Ok(v.to_le_bytes().to_vec());
+    }
+    if let Ok(b) = obj.extract::<Vec<u8>>() {
+        return Ok(b);
+    }
+    Err(pyo3::exceptions::PyValueError::new_err(
+        "hash must be an int or bytes",
+    ))
 }
 
 /// Compute a normalized color histogram for an RGB image.
@@ -254,12 +834,7 @@ fn hamming_distance(hash_a: u64, hash_b: u64) -> u32 {
 ///     Flattened histogram of length ``3 * bins``, normalized to sum to 1.
 #[pyfunction]
 #[pyo3(signature = (data, width, height, bins=16))]
-fn color_histogram(
-    data: &[u8],
-    width: usize,
-    height: usize,
-    bins: usize,
-) -> PyResult<Vec<f64>> {
+fn color_histogram(data: &[u8], width: usize, height: usize, bins: usize) -> PyResult<Vec<f64>> {
     if data.len() != width * height * 3 {
         return Err(pyo3::exceptions::PyValueError::new_err(
             "data length must equal width * height * 3 (RGB)",
@@ -325,10 +900,10 @@ fn edge_density(data: &[u8], width: usize, height: usize) -> PyResult<f64> {
     for y in 1..height - 1 {
         for x in 1..width - 1 {
             let idx = |dy: usize, dx: usize| gray[(y + dy - 1) * width + (x + dx - 1)];
-            let gx = -idx(0, 0) + idx(0, 2) - 2.0 * idx(1, 0) + 2.0 * idx(1, 2) - idx(2, 0)
-                + idx(2, 2);
-            let gy = -idx(0, 0) - 2.0 * idx(0, 1) - idx(0, 2) + idx(2, 0) + 2.0 * idx(2, 1)
-                + idx(2, 2);
+            let gx =
+                -idx(0, 0) + idx(0, 2) - 2.0 * idx(1, 0) + 2.0 * idx(1, 2) - idx(2, 0) + idx(2, 2);
+            let gy =
+                -idx(0, 0) - 2.0 * idx(0, 1) - idx(0, 2) + idx(2, 0) + 2.0 * idx(2, 1) + idx(2, 2);
             let mag = (gx * gx + gy * gy).sqrt();
             if mag > threshold {
                 edge_count += 1;
@@ -348,20 +923,32 @@ fn edge_density(data: &[u8], width: usize, height: usize) -> PyResult<f64> {
 /// ----------
 /// images : list[tuple[bytes, int, int]]
 ///     List of ``(data, width, height)`` tuples (RGB only).
+/// algo : str, default "average"
+///     Hash algorithm; see `perceptual_hash`.
+/// hash_size : int, default 8
+///     Hash grid side length; see `perceptual_hash`.
 ///
 /// Returns
 /// -------
-/// list[int]
+/// list[int | bytes]
 ///     Corresponding perceptual hashes.
 #[pyfunction]
+#[pyo3(signature = (images, algo="average", hash_size=8))]
 fn batch_perceptual_hash(
+    py: Python<'_>,
     images: Vec<(Vec<u8>, usize, usize)>,
-) -> PyResult<Vec<u64>> {
-    let results: Vec<Result<u64, PyErr>> = images
+    algo: &str,
+    hash_size: usize,
+) -> PyResult<Vec<PyObject>> {
+    let results: Vec<Result<Vec<bool>, PyErr>> = images
         .par_iter()
-        .map(|(data, w, h)| perceptual_hash(data.as_slice(), *w, *h))
+        .map(|(data, w, h)| compute_phash_bits(data.as_slice(), *w, *h, algo, hash_size))
         .collect();
-    results.into_iter().collect()
+    let bits_list: Vec<Vec<bool>> = results.into_iter().collect::<Result<_, _>>()?;
+    bits_list
+        .iter()
+        .map(|bits| hash_bits_to_pyobject(py, bits))
+        .collect()
 }
 
 /// Compute color histograms for a batch of RGB images in parallel.
@@ -390,6 +977,520 @@ fn batch_color_histogram(
     results.into_iter().collect()
 }
 
+// ---------------------------------------------------------------------------
+// Palette quantization
+// ---------------------------------------------------------------------------
+
+/// An axis-aligned box of histogram entries used by median-cut.
+///
+/// Colors are stored as `[r, g, b, a]` (channel 3 is `255` for RGB images)
+/// alongside their pixel count so boxes can be split and averaged without
+/// re-scanning the source image.
+#[derive(Clone)]
+struct ColorBox {
+    entries: Vec<([u8; 4], u64)>,
+}
+
+impl ColorBox {
+    fn weight(&self) -> u64 {
+        self.entries.iter().map(|(_, n)| *n).sum()
+    }
+
+    fn channel_range(&self, channels: usize) -> [(u8, u8); 4] {
+        let mut ranges = [(255u8, 0u8); 4];
+        for (color, _) in &self.entries {
+            for c in 0..channels {
+                ranges[c].0 = ranges[c].0.min(color[c]);
+                ranges[c].1 = ranges[c].1.max(color[c]);
+            }
+        }
+        ranges
+    }
+
+    fn longest_axis(&self, channels: usize) -> usize {
+        let ranges = self.channel_range(channels);
+        (0..channels)
+            .max_by_key(|&c| ranges[c].1 as i32 - ranges[c].0 as i32)
+            .unwrap_or(0)
+    }
+
+    fn weighted_average(&self, channels: usize) -> [u8; 4] {
+        let total = self.weight().max(1);
+        let mut sums = [0u64; 4];
+        for (color, n) in &self.entries {
+            for c in 0..channels {
+                sums[c] += color[c] as u64 * n;
+            }
+        }
+        let mut avg = [0u8; 4];
+        for c in 0..channels {
+            avg[c] = clamp_u8(sums[c] as f64 / total as f64);
+        }
+        if channels < 4 {
+            avg[3] = 255;
+        }
+        avg
+    }
+
+    /// Split at the weighted median along the longest channel axis, so each
+    /// half carries roughly equal pixel weight rather than equal entry count.
+    fn split(mut self, channels: usize) -> (ColorBox, ColorBox) {
+        let axis = self.longest_axis(channels);
+        self.entries.sort_by_key(|(color, _)| color[axis]);
+        let total_weight: u64 = self.entries.iter().map(|(_, n)| *n).sum();
+        let mut acc = 0u64;
+        let mut split_at = self.entries.len() / 2;
+        for (i, (_, n)) in self.entries.iter().enumerate() {
+            acc += n;
+            if acc * 2 >= total_weight {
+                split_at = (i + 1).clamp(1, self.entries.len() - 1);
+                break;
+            }
+        }
+        let right = self.entries.split_off(split_at);
+        (
+            ColorBox {
+                entries: self.entries,
+            },
+            ColorBox { entries: right },
+        )
+    }
+}
+
+/// Median-cut: repeatedly split the box with the largest weighted volume
+/// (pixel count times bounding-box volume) until `max_colors` boxes exist.
+fn median_cut(histogram: Vec<([u8; 4], u64)>, max_colors: usize, channels: usize) -> Vec<ColorBox> {
+    let mut boxes = vec![ColorBox { entries: histogram }];
+    while boxes.len() < max_colors {
+        let mut best_idx = None;
+        let mut best_score = -1.0f64;
+        for (i, b) in boxes.iter().enumerate() {
+            if b.entries.len() < 2 {
+                continue;
+            }
+            let ranges = b.channel_range(channels);
+            let volume: f64 = ranges[..channels]
+                .iter()
+                .map(|&(lo, hi)| (hi as f64 - lo as f64) + 1.0)
+                .product();
+            let score = volume * b.weight() as f64;
+            if score > best_score {
+                best_score = score;
+                best_idx = Some(i);
+            }
+        }
+        let Some(idx) = best_idx else {
+            break;
+        };
+        let (a, b) = boxes.remove(idx).split(channels);
+        boxes.push(a);
+        boxes.push(b);
+    }
+    boxes
+}
+
+/// Read one pixel at byte offset `off` into a `[u8; 4]`, padding alpha to 255.
+fn read_color(data: &[u8], off: usize, channels: usize) -> [u8; 4] {
+    let mut color = [0u8; 4];
+    color[..channels].copy_from_slice(&data[off..off + channels]);
+    if channels < 4 {
+        color[3] = 255;
+    }
+    color
+}
+
+fn color_dist_sq(a: [u8; 4], b: [u8; 4], channels: usize) -> u32 {
+    (0..channels)
+        .map(|c| {
+            let d = a[c] as i32 - b[c] as i32;
+            (d * d) as u32
+        })
+        .sum()
+}
+
+fn nearest_palette_index(color: [u8; 4], palette: &[[u8; 4]], channels: usize) -> usize {
+    palette
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, &p)| color_dist_sq(color, p, channels))
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Refine a median-cut palette with a few Lloyd (k-means) iterations:
+/// reassign every histogram entry to its nearest palette color, then
+/// recompute each palette color as the pixel-count-weighted mean of its
+/// assigned entries.
+fn kmeans_refine(
+    palette: &mut [[u8; 4]],
+    histogram: &[([u8; 4], u64)],
+    channels: usize,
+    iters: usize,
+) {
+    for _ in 0..iters {
+        let mut sums = vec![[0u64; 4]; palette.len()];
+        let mut counts = vec![0u64; palette.len()];
+        for (color, n) in histogram {
+            let nearest = nearest_palette_index(*color, palette, channels);
+            for c in 0..channels {
+                sums[nearest][c] += color[c] as u64 * n;
+            }
+            counts[nearest] += n;
+        }
+        for (i, p) in palette.iter_mut().enumerate() {
+            if counts[i] == 0 {
+                continue;
+            }
+            for c in 0..channels {
+                p[c] = clamp_u8(sums[i][c] as f64 / counts[i] as f64);
+            }
+        }
+    }
+}
+
+/// Remap an image to palette indices with Floyd-Steinberg error diffusion,
+/// scanning serpentine (alternating direction per row) to avoid directional
+/// artifacts.
+fn floyd_steinberg_remap(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    palette: &[[u8; 4]],
+) -> Vec<u8> {
+    let mut buf: Vec<f64> = data.iter().map(|&v| v as f64).collect();
+    let mut indices = vec![0u8; width * height];
+    for y in 0..height {
+        let forward = y % 2 == 0;
+        let dir: isize = if forward { 1 } else { -1 };
+        let xs: Vec<usize> = if forward {
+            (0..width).collect()
+        } else {
+            (0..width).rev().collect()
+        };
+        for x in xs {
+            let off = (y * width + x) * channels;
+            let mut color = [0u8; 4];
+            for c in 0..channels {
+                color[c] = clamp_u8(buf[off + c]);
+            }
+            let idx = nearest_palette_index(color, palette, channels);
+            indices[y * width + x] = idx as u8;
+            let p = palette[idx];
+            for c in 0..channels {
+                let err = buf[off + c] - p[c] as f64;
+                let fwd_x = x as isize + dir;
+                let back_x = x as isize - dir;
+                if fwd_x >= 0 && (fwd_x as usize) < width {
+                    buf[(y * width + fwd_x as usize) * channels + c] += err * 7.0 / 16.0;
+                }
+                if y + 1 < height {
+                    if back_x >= 0 && (back_x as usize) < width {
+                        buf[((y + 1) * width + back_x as usize) * channels + c] += err * 3.0 / 16.0;
+                    }
+                    buf[((y + 1) * width + x) * channels + c] += err * 5.0 / 16.0;
+                    if fwd_x >= 0 && (fwd_x as usize) < width {
+                        buf[((y + 1) * width + fwd_x as usize) * channels + c] += err * 1.0 / 16.0;
+                    }
+                }
+            }
+        }
+    }
+    indices
+}
+
+/// (palette_bytes, indices_bytes) pair returned by `quantize_palette`.
+type QuantizeResult = (Vec<u8>, Vec<u8>);
+
+/// Reduce an RGB(A) buffer to an indexed palette via median-cut + k-means.
+///
+/// Parameters
+/// ----------
+/// data : bytes
+///     Raw pixel data in row-major order (R, G, B[, A] per pixel).
+/// width : int
+///     Image width in pixels.
+/// height : int
+///     Image height in pixels.
+/// channels : int
+///     Number of channels (3 for RGB, 4 for RGBA).
+/// max_colors : int
+///     Maximum palette size (1-256), e.g. 16 or 256 for PS2 textures.
+/// dither : bool, default False
+///     Apply Floyd-Steinberg error diffusion when remapping pixels.
+/// kmeans_iters : int, default 4
+///     Number of Lloyd refinement iterations run on the median-cut palette.
+///
+/// Returns
+/// -------
+/// tuple[bytes, bytes]
+///     (palette_bytes, indices_bytes): the palette packed as `channels`
+///     bytes per entry, and one index byte per pixel.
+#[pyfunction]
+#[pyo3(signature = (data, width, height, channels, max_colors, dither=false, kmeans_iters=4))]
+fn quantize_palette(
+    data: &[u8],
+    width: usize,
+    height: usize,
+    channels: usize,
+    max_colors: usize,
+    dither: bool,
+    kmeans_iters: usize,
+) -> PyResult<QuantizeResult> {
+    if channels != 3 && channels != 4 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "channels must be 3 (RGB) or 4 (RGBA)",
+        ));
+    }
+    let expected_len = width * height * channels;
+    if data.len() != expected_len {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "data length {} does not match {}x{}x{}={}",
+            data.len(),
+            width,
+            height,
+            channels,
+            expected_len,
+        )));
+    }
+    if max_colors == 0 || max_colors > 256 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "max_colors must be between 1 and 256",
+        ));
+    }
+
+    let mut counts: HashMap<[u8; 4], u64> = HashMap::new();
+    for i in 0..width * height {
+        let color = read_color(data, i * channels, channels);
+        *counts.entry(color).or_insert(0) += 1;
+    }
+    let histogram: Vec<([u8; 4], u64)> = counts.into_iter().collect();
+    let max_colors = max_colors.min(histogram.len().max(1));
+
+    let boxes = median_cut(histogram.clone(), max_colors, channels);
+    let mut palette: Vec<[u8; 4]> = boxes.iter().map(|b| b.weighted_average(channels)).collect();
+    if kmeans_iters > 0 {
+        kmeans_refine(&mut palette, &histogram, channels, kmeans_iters);
+    }
+
+    let indices = if dither {
+        floyd_steinberg_remap(data, width, height, channels, &palette)
+    } else {
+        (0..width * height)
+            .into_par_iter()
+            .map(|i| {
+                let color = read_color(data, i * channels, channels);
+                nearest_palette_index(color, &palette, channels) as u8
+            })
+            .collect()
+    };
+
+    let mut palette_bytes = Vec::with_capacity(palette.len() * channels);
+    for p in &palette {
+        for c in p.iter().take(channels) {
+            palette_bytes.push(*c);
+        }
+    }
+
+    Ok((palette_bytes, indices))
+}
+
+/// Quantize a batch of RGB(A) images to indexed palettes in parallel.
+///
+/// Parameters
+/// ----------
+/// images : list[tuple[bytes, int, int, int]]
+///     List of ``(data, width, height, channels)`` tuples.
+/// max_colors : int
+///     Maximum palette size (1-256) applied to every image.
+/// dither : bool, default False
+///     Apply Floyd-Steinberg error diffusion when remapping pixels.
+/// kmeans_iters : int, default 4
+///     Number of Lloyd refinement iterations run on each median-cut palette.
+///
+/// Returns
+/// -------
+/// list[tuple[bytes, bytes]]
+///     Corresponding (palette_bytes, indices_bytes) pairs.
+#[pyfunction]
+#[pyo3(signature = (images, max_colors, dither=false, kmeans_iters=4))]
+fn batch_quantize_palette(
+    images: Vec<(Vec<u8>, usize, usize, usize)>,
+    max_colors: usize,
+    dither: bool,
+    kmeans_iters: usize,
+) -> PyResult<Vec<QuantizeResult>> {
+    let results: Vec<Result<QuantizeResult, PyErr>> = images
+        .par_iter()
+        .map(|(data, w, h, channels)| {
+            quantize_palette(
+                data.as_slice(),
+                *w,
+                *h,
+                *channels,
+                max_colors,
+                dither,
+                kmeans_iters,
+            )
+        })
+        .collect();
+    results.into_iter().collect()
+}
+
+// ---------------------------------------------------------------------------
+// Near-duplicate clustering
+// ---------------------------------------------------------------------------
+
+/// Union-find (disjoint-set) over a fixed number of elements, with union by
+/// rank and path compression.
+struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(n: usize) -> Self {
+        DisjointSet {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+        }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return;
+        }
+        match self.rank[ra].cmp(&self.rank[rb]) {
+            std::cmp::Ordering::Less => self.parent[ra] = rb,
+            std::cmp::Ordering::Greater => self.parent[rb] = ra,
+            std::cmp::Ordering::Equal => {
+                self.parent[rb] = ra;
+                self.rank[ra] += 1;
+            }
+        }
+    }
+}
+
+/// Group hashes into near-duplicate clusters by Hamming distance.
+///
+/// Since an all-pairs scan is O(n^2), hashes are bucketed first using the
+/// pigeonhole principle: each 64-bit hash is split into `max_distance + 1`
+/// contiguous segments, indexed by (segment position, segment value). Two
+/// hashes within `max_distance` bits must collide in at least one segment,
+/// so only candidates sharing a bucket are compared (in parallel), each
+/// verified with the real Hamming distance before unioning.
+///
+/// Parameters
+/// ----------
+/// hashes : list[int]
+///     64-bit perceptual hashes (e.g. from `batch_perceptual_hash` with the
+///     default hash size).
+/// max_distance : int
+///     Maximum Hamming distance (in bits) for two hashes to be grouped;
+///     must be <= 64 since hashes are 64-bit.
+///
+/// Returns
+/// -------
+/// list[list[int]]
+///     Groups of input indices whose hashes are mutually reachable within
+///     `max_distance` bits, via transitive (union-find) closure.
+#[pyfunction]
+fn cluster_by_similarity(hashes: Vec<u64>, max_distance: u32) -> PyResult<Vec<Vec<usize>>> {
+    if max_distance > 64 {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "max_distance must be <= 64 (hashes are 64-bit)",
+        ));
+    }
+    let n = hashes.len();
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+
+    let k = (max_distance + 1).max(1) as usize;
+    let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+    for (i, &h) in hashes.iter().enumerate() {
+        for seg in 0..k {
+            // Exactly `k` segments covering all 64 bits; when k > 64 some
+            // segments are empty and put every hash in one shared bucket.
+            let shift = seg * 64 / k;
+            let width = (seg + 1) * 64 / k - shift;
+            let mask = if width >= 64 {
+                u64::MAX
+            } else {
+                (1u64 << width) - 1
+            };
+            let seg_val = (h >> shift) & mask;
+            buckets.entry((seg, seg_val)).or_default().push(i);
+        }
+    }
+
+    let hashes_ref = &hashes;
+    let candidate_pairs: Vec<(usize, usize)> = buckets
+        .par_iter()
+        .flat_map_iter(|(_, members)| {
+            let mut pairs = Vec::new();
+            for a in 0..members.len() {
+                for b in (a + 1)..members.len() {
+                    pairs.push((members[a], members[b]));
+                }
+            }
+            pairs.into_iter()
+        })
+        .filter(|&(ia, ib)| (hashes_ref[ia] ^ hashes_ref[ib]).count_ones() <= max_distance)
+        .collect();
+
+    let mut dsu = DisjointSet::new(n);
+    for (ia, ib) in candidate_pairs {
+        dsu.union(ia, ib);
+    }
+
+    let mut groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..n {
+        let root = dsu.find(i);
+        groups.entry(root).or_default().push(i);
+    }
+    let mut result: Vec<Vec<usize>> = groups.into_values().collect();
+    result.sort_by_key(|g| g[0]);
+    Ok(result)
+}
+
+/// Find the closest match to a query hash within a precomputed corpus.
+///
+/// Parameters
+/// ----------
+/// query_hash : int
+///     64-bit perceptual hash to look up.
+/// hashes : list[int]
+///     Corpus of 64-bit perceptual hashes to search.
+///
+/// Returns
+/// -------
+/// tuple[int, int]
+///     (index, distance) of the closest match in `hashes`.
+#[pyfunction]
+fn find_nearest(query_hash: u64, hashes: Vec<u64>) -> PyResult<(usize, u32)> {
+    if hashes.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err(
+            "hashes must not be empty",
+        ));
+    }
+    let (index, distance) = hashes
+        .par_iter()
+        .enumerate()
+        .map(|(i, &h)| (i, (query_hash ^ h).count_ones()))
+        .min_by_key(|&(_, dist)| dist)
+        .unwrap();
+    Ok((index, distance))
+}
+
 // ---------------------------------------------------------------------------
 // Python module
 // ---------------------------------------------------------------------------
@@ -397,11 +1498,15 @@ fn batch_color_histogram(
 /// Native Rust acceleration module for PS2 texture processing.
 ///
 /// Provides fast Lanczos upscaling, perceptual hashing, color histograms,
-/// edge density computation, and parallel batch operations.
+/// edge density computation, palette quantization, near-duplicate
+/// clustering, and parallel batch operations.
 #[pymodule]
 fn texture_ops(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Upscaling
     m.add_function(wrap_pyfunction!(lanczos_upscale, m)?)?;
+    m.add_class::<Resizer>()?;
+    m.add_function(wrap_pyfunction!(srgb_to_linear, m)?)?;
+    m.add_function(wrap_pyfunction!(linear_to_srgb, m)?)?;
 
     // Feature extraction
     m.add_function(wrap_pyfunction!(perceptual_hash, m)?)?;
@@ -413,5 +1518,13 @@ fn texture_ops(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(batch_perceptual_hash, m)?)?;
     m.add_function(wrap_pyfunction!(batch_color_histogram, m)?)?;
 
+    // Palette quantization
+    m.add_function(wrap_pyfunction!(quantize_palette, m)?)?;
+    m.add_function(wrap_pyfunction!(batch_quantize_palette, m)?)?;
+
+    // Near-duplicate clustering
+    m.add_function(wrap_pyfunction!(cluster_by_similarity, m)?)?;
+    m.add_function(wrap_pyfunction!(find_nearest, m)?)?;
+
     Ok(())
 }